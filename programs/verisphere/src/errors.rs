@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum VeriSphereError {
+    #[msg("Stake amount must be greater than zero")]
+    ZeroStakeAmount,
+    #[msg("Post has already been resolved")]
+    PostAlreadyResolved,
+    #[msg("Signer is not the stake entry owner or an active authorized staker")]
+    UnauthorizedStaker,
+    #[msg("Stake amount exceeds the configured per-transaction limit")]
+    MaxStakePerTxExceeded,
+    #[msg("Stake would push the post's total staked amount past the configured cap")]
+    MaxTotalStakePerPostExceeded,
+    #[msg("Stake entry is still within its withdrawal lockout")]
+    StakeLocked,
+    #[msg("Stake entry has no escrowed amount to withdraw")]
+    NothingToWithdraw,
+}
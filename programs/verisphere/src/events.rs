@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct PostInitialized {
+    pub post: Pubkey,
+    pub creator: Pubkey,
+    pub auto_stake: u64,
+}
+
+#[event]
+pub struct StakeInfo {
+    pub post: Pubkey,
+    pub staker: Pubkey,
+    pub amount: u64,
+    pub agree: bool,
+    pub voting_power: u64,
+    pub voting_power_baseline: u64,
+}
+
+#[event]
+pub struct PostResolved {
+    pub post: Pubkey,
+    pub outcome: bool,
+    pub total_agree_power: u64,
+    pub total_disagree_power: u64,
+}
@@ -1,4 +1,13 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+pub mod errors;
+pub mod events;
+pub mod state;
+
+use errors::VeriSphereError;
+use events::{PostInitialized, PostResolved, StakeInfo};
+use state::{Config, LockingInfo, Post, StakeEntry};
 
 //declare_id!("BjoPCPfAqaK9tfiyMGEecFi4HtA1LQEX6WdSWxL2ETyT");
 declare_id!("Cf9Lf8pCfpV9iEajzLA84ZizQLK56N1r2PBfja5qegFY");
@@ -7,27 +16,368 @@ declare_id!("Cf9Lf8pCfpV9iEajzLA84ZizQLK56N1r2PBfja5qegFY");
 pub mod verisphere {
     use super::*;
 
-    pub fn initialize_post(_ctx: Context<InitializePost>, _stake: u64) -> Result<()> {
-        // Placeholder: 1 VSP auto-stake fee
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        max_stake_per_tx: u64,
+        max_total_stake_per_post: u64,
+        auto_stake_fee: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.max_stake_per_tx = max_stake_per_tx;
+        config.max_total_stake_per_post = max_total_stake_per_post;
+        config.auto_stake_fee = auto_stake_fee;
+        config.bump = ctx.bumps.config;
+        Ok(())
+    }
+
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        max_stake_per_tx: u64,
+        max_total_stake_per_post: u64,
+        auto_stake_fee: u64,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.max_stake_per_tx = max_stake_per_tx;
+        config.max_total_stake_per_post = max_total_stake_per_post;
+        config.auto_stake_fee = auto_stake_fee;
+        Ok(())
+    }
+
+    pub fn initialize_post(ctx: Context<InitializePost>, claim_hash: [u8; 32]) -> Result<()> {
+        let auto_stake = ctx.accounts.config.auto_stake_fee;
+
+        // `#[account(init, space = 8 + Post::INIT_SPACE, ...)]` below already
+        // allocates exactly that space and fails the instruction if `post` is
+        // already created, so there's nothing left for this handler to check.
+
+        let post = &mut ctx.accounts.post;
+        post.creator = ctx.accounts.payer.key();
+        post.claim_hash = claim_hash;
+        post.created_at = Clock::get()?.unix_timestamp;
+        post.total_agree_power = 0;
+        post.total_disagree_power = 0;
+        post.total_staked = 0;
+        post.resolved = false;
+        post.outcome = false;
+        post.bump = ctx.bumps.post;
+
+        emit!(PostInitialized {
+            post: post.key(),
+            creator: post.creator,
+            auto_stake,
+        });
+
         Ok(())
     }
 
-    pub fn stake(_ctx: Context<Stake>, _amount: u64, _agree: bool) -> Result<()> {
-        // Placeholder: Add stake to agree or disagree
+    pub fn stake(ctx: Context<Stake>, amount: u64, agree: bool, locking: LockingInfo) -> Result<()> {
+        require!(amount > 0, VeriSphereError::ZeroStakeAmount);
+        require!(!ctx.accounts.post.resolved, VeriSphereError::PostAlreadyResolved);
+        require!(
+            amount <= ctx.accounts.config.max_stake_per_tx,
+            VeriSphereError::MaxStakePerTxExceeded
+        );
+        require!(
+            ctx.accounts.post.total_staked.saturating_add(amount)
+                <= ctx.accounts.config.max_total_stake_per_post,
+            VeriSphereError::MaxTotalStakePerPostExceeded
+        );
+
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        let slot = clock.slot;
+
+        let entry = &mut ctx.accounts.stake_entry;
+        let is_new_entry = !entry.is_initialized();
+        if is_new_entry {
+            entry.owner = ctx.accounts.owner.key();
+            entry.authorized_staker = ctx.accounts.owner.key();
+            entry.delegate_expires_at = None;
+        }
+        require!(
+            entry.can_stake(&ctx.accounts.authority.key(), now),
+            VeriSphereError::UnauthorizedStaker
+        );
+
+        // Funds always move out of the owner's token account; a delegate signing
+        // here must already hold an SPL-level `Approve` from the owner over
+        // `staker_token_account`, so the owner stays the custodian of record.
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.staker_token_account.to_account_info(),
+                    to: ctx.accounts.escrow_token_account.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let entry = &mut ctx.accounts.stake_entry;
+        // A zeroed balance (a brand-new entry, or one drained by a prior
+        // `withdraw`) is always a fresh position: the lockout escalation must
+        // restart at INITIAL_LOCKOUT_SLOTS rather than doubling a stale,
+        // already-zeroed `lockout_offset` (which would stay zero forever).
+        let has_position = entry.amount > 0;
+        let old_voting_power = entry.voting_power;
+        let old_agree = entry.agree;
+
+        let (new_voting_power, new_lockout_offset) =
+            entry.compute_stake_update(amount, agree, &locking, now);
+        let total_amount = entry.amount.saturating_add(amount);
+
+        entry.post = ctx.accounts.post.key();
+        entry.amount = total_amount;
+        entry.agree = agree;
+        entry.locking = locking;
+        entry.voting_power = new_voting_power;
+        entry.last_stake_slot = slot;
+        entry.lockout_offset = new_lockout_offset;
+        entry.bump = ctx.bumps.stake_entry;
+        let owner = entry.owner;
+
+        let post = &mut ctx.accounts.post;
+        if has_position {
+            if old_agree {
+                post.total_agree_power = post.total_agree_power.saturating_sub(old_voting_power);
+            } else {
+                post.total_disagree_power =
+                    post.total_disagree_power.saturating_sub(old_voting_power);
+            }
+        }
+        if agree {
+            post.total_agree_power = post.total_agree_power.saturating_add(new_voting_power);
+        } else {
+            post.total_disagree_power =
+                post.total_disagree_power.saturating_add(new_voting_power);
+        }
+        post.total_staked = post.total_staked.saturating_add(amount);
+
+        emit!(StakeInfo {
+            post: post.key(),
+            staker: owner,
+            amount,
+            agree,
+            voting_power: new_voting_power,
+            voting_power_baseline: total_amount,
+        });
+
+        Ok(())
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
+        let entry = &ctx.accounts.stake_entry;
+        require!(entry.amount > 0, VeriSphereError::NothingToWithdraw);
+
+        let clock = Clock::get()?;
+        require!(clock.slot >= entry.unlock_slot(), VeriSphereError::StakeLocked);
+        require!(
+            entry.locking.is_elapsed(clock.unix_timestamp),
+            VeriSphereError::StakeLocked
+        );
+
+        let post_key = ctx.accounts.post.key();
+        let seeds: &[&[u8]] = &[
+            b"escrow_authority",
+            post_key.as_ref(),
+            &[ctx.bumps.escrow_authority],
+        ];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.escrow_token_account.to_account_info(),
+                    to: ctx.accounts.owner_token_account.to_account_info(),
+                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                },
+                &[seeds],
+            ),
+            entry.amount,
+        )?;
+
+        let amount = entry.amount;
+        let voting_power = entry.voting_power;
+        let agree = entry.agree;
+
+        let post = &mut ctx.accounts.post;
+        if agree {
+            post.total_agree_power = post.total_agree_power.saturating_sub(voting_power);
+        } else {
+            post.total_disagree_power = post.total_disagree_power.saturating_sub(voting_power);
+        }
+        post.total_staked = post.total_staked.saturating_sub(amount);
+
+        let entry = &mut ctx.accounts.stake_entry;
+        entry.amount = 0;
+        entry.voting_power = 0;
+        entry.lockout_offset = 0;
+
         Ok(())
     }
+
+    pub fn authorize_staker(
+        ctx: Context<AuthorizeStaker>,
+        new_staker: Pubkey,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        let entry = &mut ctx.accounts.stake_entry;
+        entry.authorized_staker = new_staker;
+        entry.delegate_expires_at = expires_at;
+        Ok(())
+    }
+
+    pub fn resolve_post(ctx: Context<ResolvePost>, outcome: bool) -> Result<()> {
+        let post = &mut ctx.accounts.post;
+        require!(!post.resolved, VeriSphereError::PostAlreadyResolved);
+
+        post.resolved = true;
+        post.outcome = outcome;
+
+        emit!(PostResolved {
+            post: post.key(),
+            outcome,
+            total_agree_power: post.total_agree_power,
+            total_disagree_power: post.total_disagree_power,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+    #[account(mut, seeds = [b"config"], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
 }
 
 #[derive(Accounts)]
+#[instruction(claim_hash: [u8; 32])]
 pub struct InitializePost<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Post::INIT_SPACE,
+        seeds = [b"post", claim_hash.as_ref()],
+        bump,
+    )]
+    pub post: Account<'info, Post>,
+    pub mint: Account<'info, Mint>,
+    /// CHECK: PDA authority over the post's escrow token account, derived deterministically.
+    #[account(seeds = [b"escrow_authority", post.key().as_ref()], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"escrow", post.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = escrow_authority,
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct Stake<'info> {
+    /// Either the position owner or their currently-authorized delegate.
     #[account(mut)]
-    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    /// CHECK: only used to derive the stake-entry PDA and to identify the
+    /// position's owner of record; `stake` enforces the actual authorization.
+    pub owner: UncheckedAccount<'info>,
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub post: Account<'info, Post>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + StakeEntry::INIT_SPACE,
+        seeds = [b"stake", post.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub stake_entry: Account<'info, StakeEntry>,
+    pub mint: Account<'info, Mint>,
+    #[account(mut, constraint = staker_token_account.owner == owner.key())]
+    pub staker_token_account: Account<'info, TokenAccount>,
+    /// CHECK: PDA authority over the post's escrow token account, derived deterministically.
+    #[account(seeds = [b"escrow_authority", post.key().as_ref()], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"escrow", post.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.owner == escrow_authority.key(),
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub post: Account<'info, Post>,
+    #[account(
+        mut,
+        seeds = [b"stake", post.key().as_ref(), owner.key().as_ref()],
+        bump = stake_entry.bump,
+        has_one = owner,
+        has_one = post,
+    )]
+    pub stake_entry: Account<'info, StakeEntry>,
+    /// CHECK: PDA authority over the post's escrow token account, derived deterministically.
+    #[account(seeds = [b"escrow_authority", post.key().as_ref()], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"escrow", post.key().as_ref()],
+        bump,
+        constraint = escrow_token_account.owner == escrow_authority.key(),
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    #[account(mut, constraint = owner_token_account.owner == owner.key())]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct AuthorizeStaker<'info> {
+    pub owner: Signer<'info>,
+    #[account(mut, has_one = owner)]
+    pub stake_entry: Account<'info, StakeEntry>,
+}
+
+#[derive(Accounts)]
+pub struct ResolvePost<'info> {
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"config"], bump = config.bump, has_one = admin)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub post: Account<'info, Post>,
+}
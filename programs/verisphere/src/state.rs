@@ -0,0 +1,305 @@
+use anchor_lang::prelude::*;
+
+/// Longest remaining lockup, in seconds, that still earns extra conviction.
+/// Mirrors the saturation window used by voter-stake-registry's max-lockup bonus.
+pub const MAX_LOCKUP_SECS: i64 = 5 * 365 * 24 * 60 * 60;
+
+/// Starting withdrawal lockout, in slots, applied the first time a position is
+/// staked. Mirrors Tower's `INITIAL_LOCKOUT`.
+pub const INITIAL_LOCKOUT_SLOTS: u64 = 2;
+
+/// Cap on the escalating lockout offset, mirroring Tower's `MAX_LOCKOUT_HISTORY`
+/// saturation (doubling stops once withdrawals would be locked out for ~this long).
+pub const MAX_LOCKOUT_SLOTS: u64 = 1 << 31;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Post {
+    pub creator: Pubkey,
+    pub claim_hash: [u8; 32],
+    pub created_at: i64,
+    pub total_agree_power: u64,
+    pub total_disagree_power: u64,
+    /// Sum of raw staked amounts (not voting power) across every `StakeEntry` on
+    /// this post, checked against `Config::max_total_stake_per_post`.
+    pub total_staked: u64,
+    pub resolved: bool,
+    pub outcome: bool,
+    pub bump: u8,
+}
+
+/// Admin-owned protocol guardrails, following the tunable-constant pattern of
+/// snarkVM's `MAX_FEE` / `TRANSACTION_SPEND_LIMIT`.
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub max_stake_per_tx: u64,
+    pub max_total_stake_per_post: u64,
+    pub auto_stake_fee: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakeEntry {
+    pub post: Pubkey,
+    pub owner: Pubkey,
+    /// Who may currently call `stake` on the owner's behalf. Defaults to `owner`;
+    /// rotated via `authorize_staker`, similar to a vote account's authorized voter.
+    pub authorized_staker: Pubkey,
+    /// Expiry for the current `authorized_staker` delegation, when it differs from
+    /// `owner`. `None` means the delegation does not expire.
+    pub delegate_expires_at: Option<i64>,
+    pub amount: u64,
+    pub agree: bool,
+    pub locking: LockingInfo,
+    /// Voting power this entry currently contributes to `post.total_{agree,disagree}_power`.
+    /// Tracked separately from `amount` so it can be removed precisely on withdrawal or
+    /// side-switch forfeiture.
+    pub voting_power: u64,
+    /// Slot of the most recent `stake` call against this entry.
+    pub last_stake_slot: u64,
+    /// Tower-style escalating withdrawal lockout: doubles on every additional
+    /// same-side stake (capped at `MAX_LOCKOUT_SLOTS`), resets on a side switch.
+    /// Funds unlock at `last_stake_slot + lockout_offset`.
+    pub lockout_offset: u64,
+    pub bump: u8,
+}
+
+impl StakeEntry {
+    /// True once `stake` has run at least once for this entry.
+    pub fn is_initialized(&self) -> bool {
+        self.owner != Pubkey::default()
+    }
+
+    /// Slot at which this entry's escrowed stake may be withdrawn.
+    pub fn unlock_slot(&self) -> u64 {
+        self.last_stake_slot.saturating_add(self.lockout_offset)
+    }
+
+    /// Whether `signer` is currently allowed to stake on behalf of `owner`: the
+    /// owner can always act, and a delegated `authorized_staker` can act so long
+    /// as its time-bounded window (if any) hasn't expired.
+    pub fn can_stake(&self, signer: &Pubkey, now: i64) -> bool {
+        if signer == &self.owner {
+            return true;
+        }
+        if signer != &self.authorized_staker || self.authorized_staker == self.owner {
+            return false;
+        }
+        match self.delegate_expires_at {
+            Some(expires_at) => now < expires_at,
+            None => true,
+        }
+    }
+
+    /// Resulting voting power and lockout offset for a `stake` call against this
+    /// entry, given the new amount being added, the side staked, and the lockup
+    /// schedule to apply. `self` must reflect the entry's state *before* this
+    /// stake (i.e. `self.amount` is the escrowed balance still held, which is
+    /// zero once a full `withdraw` has gone through) — a zeroed balance is always
+    /// treated as a fresh position, so the lockout escalation restarts cleanly
+    /// instead of being permanently pinned at zero by a stale `lockout_offset`.
+    pub fn compute_stake_update(
+        &self,
+        new_amount: u64,
+        agree: bool,
+        locking: &LockingInfo,
+        now: i64,
+    ) -> (u64, u64) {
+        let has_position = self.amount > 0;
+        let total_amount = self.amount.saturating_add(new_amount);
+        let same_side = has_position && self.agree == agree;
+
+        if !has_position {
+            (locking.voting_power(total_amount, now), INITIAL_LOCKOUT_SLOTS)
+        } else if same_side {
+            (
+                locking.voting_power(total_amount, now),
+                self.lockout_offset.saturating_mul(2).min(MAX_LOCKOUT_SLOTS),
+            )
+        } else {
+            (total_amount, INITIAL_LOCKOUT_SLOTS)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(amount: u64, agree: bool, lockout_offset: u64) -> StakeEntry {
+        StakeEntry {
+            post: Pubkey::default(),
+            owner: Pubkey::new_unique(),
+            authorized_staker: Pubkey::default(),
+            delegate_expires_at: None,
+            amount,
+            agree,
+            locking: LockingInfo::default(),
+            voting_power: 0,
+            last_stake_slot: 0,
+            lockout_offset,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn can_stake_allows_owner_always() {
+        let e = entry(0, true, 0);
+        assert!(e.can_stake(&e.owner, 100));
+    }
+
+    #[test]
+    fn can_stake_allows_active_delegate_and_rejects_expired() {
+        let mut e = entry(0, true, 0);
+        let delegate = Pubkey::new_unique();
+        e.authorized_staker = delegate;
+        e.delegate_expires_at = Some(100);
+
+        assert!(e.can_stake(&delegate, 50));
+        assert!(!e.can_stake(&delegate, 100));
+        assert!(!e.can_stake(&delegate, 200));
+
+        let stranger = Pubkey::new_unique();
+        assert!(!e.can_stake(&stranger, 50));
+    }
+
+    #[test]
+    fn is_elapsed_never_for_constant_lockup() {
+        let locking = LockingInfo {
+            amount: 0,
+            end_timestamp: None,
+            vesting: None,
+        };
+        assert!(!locking.is_elapsed(i64::MAX));
+    }
+
+    #[test]
+    fn is_elapsed_once_end_timestamp_reached() {
+        let locking = LockingInfo {
+            amount: 0,
+            end_timestamp: Some(100),
+            vesting: None,
+        };
+        assert!(!locking.is_elapsed(99));
+        assert!(locking.is_elapsed(100));
+        assert!(locking.is_elapsed(101));
+    }
+
+    #[test]
+    fn voting_power_adds_flat_bonus_for_constant_lockup() {
+        let locking = LockingInfo {
+            amount: 0,
+            end_timestamp: None,
+            vesting: None,
+        };
+        // A never-ending lockup always earns the full max-lockup bonus.
+        assert_eq!(locking.voting_power(100, 0), 200);
+    }
+
+    #[test]
+    fn voting_power_scales_with_remaining_lockup() {
+        let locking = LockingInfo {
+            amount: 0,
+            end_timestamp: Some(MAX_LOCKUP_SECS / 2),
+            vesting: None,
+        };
+        // Half the max lockup window remaining earns half the bonus.
+        assert_eq!(locking.voting_power(100, 0), 150);
+    }
+
+    #[test]
+    fn first_stake_on_fresh_entry_starts_at_initial_lockout() {
+        let e = entry(0, false, 0);
+        let locking = LockingInfo::default();
+        let (_power, lockout) = e.compute_stake_update(100, true, &locking, 0);
+        assert_eq!(lockout, INITIAL_LOCKOUT_SLOTS);
+    }
+
+    #[test]
+    fn same_side_restake_doubles_lockout_and_caps_it() {
+        let e = entry(100, true, INITIAL_LOCKOUT_SLOTS);
+        let locking = LockingInfo::default();
+        let (_power, lockout) = e.compute_stake_update(50, true, &locking, 0);
+        assert_eq!(lockout, INITIAL_LOCKOUT_SLOTS * 2);
+
+        let e_near_cap = entry(100, true, MAX_LOCKOUT_SLOTS);
+        let (_power, lockout) = e_near_cap.compute_stake_update(50, true, &locking, 0);
+        assert_eq!(lockout, MAX_LOCKOUT_SLOTS);
+    }
+
+    #[test]
+    fn switching_sides_resets_lockout_and_forfeits_bonus() {
+        let locking = LockingInfo {
+            amount: 0,
+            end_timestamp: None,
+            vesting: None,
+        };
+        let e = entry(100, true, INITIAL_LOCKOUT_SLOTS * 4);
+        let (power, lockout) = e.compute_stake_update(50, false, &locking, 0);
+        assert_eq!(lockout, INITIAL_LOCKOUT_SLOTS);
+        // Forfeits the lockup bonus: flat baseline power on the whole position,
+        // not `locking.voting_power(150, 0)` (which would be 300).
+        assert_eq!(power, 150);
+    }
+
+    #[test]
+    fn withdrawn_entry_restarts_lockout_instead_of_staying_pinned_at_zero() {
+        // Regression test: a full withdraw zeroes `amount` and `lockout_offset`.
+        // Restaking the same side afterwards must restart the Tower escalation
+        // at INITIAL_LOCKOUT_SLOTS, not compute `0 * 2 == 0` forever.
+        let withdrawn = entry(0, true, 0);
+        let locking = LockingInfo::default();
+        let (_power, lockout) = withdrawn.compute_stake_update(100, true, &locking, 0);
+        assert_eq!(lockout, INITIAL_LOCKOUT_SLOTS);
+        assert_ne!(lockout, 0);
+    }
+}
+
+/// A lockup schedule for a staked position, modeled on voter-stake-registry's
+/// `LockingInfo`. `end_timestamp: None` means the lockup never ends (constant
+/// lockup), which earns the flat maximum conviction multiplier.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Default)]
+pub struct LockingInfo {
+    pub amount: u64,
+    pub end_timestamp: Option<i64>,
+    pub vesting: Option<VestingInfo>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Default)]
+pub struct VestingInfo {
+    pub rate: u64,
+    pub next_timestamp: i64,
+}
+
+impl LockingInfo {
+    /// Whether the claimed lockup has actually elapsed. A constant (never-ending,
+    /// `end_timestamp: None`) lockup never elapses — claiming the flat max
+    /// conviction bonus means the stake is locked for good, not merely until the
+    /// Tower withdrawal lockout clears.
+    pub fn is_elapsed(&self, now: i64) -> bool {
+        match self.end_timestamp {
+            Some(end) => now >= end,
+            None => false,
+        }
+    }
+
+    /// Seconds remaining until the lockup fully unwinds. A constant (never-ending)
+    /// lockup always reports the full `MAX_LOCKUP_SECS` window.
+    pub fn remaining_secs(&self, now: i64) -> i64 {
+        match self.end_timestamp {
+            None => MAX_LOCKUP_SECS,
+            Some(end) => (end - now).max(0),
+        }
+    }
+
+    /// Voting power for `baseline` VSP staked under this lockup:
+    /// `baseline + baseline * min(remaining_lockup_secs, MAX_LOCKUP) / MAX_LOCKUP`.
+    pub fn voting_power(&self, baseline: u64, now: i64) -> u64 {
+        let remaining = self.remaining_secs(now).min(MAX_LOCKUP_SECS) as u128;
+        let bonus = (baseline as u128) * remaining / (MAX_LOCKUP_SECS as u128);
+        baseline.saturating_add(bonus as u64)
+    }
+}